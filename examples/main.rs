@@ -23,32 +23,52 @@ trait Initializable {
     fn generic<C: Component>();
 
     #[system]
+    #[add_to_schedule]
     fn needs_build(data: i32);
 
-    #[system]
-    fn build_generic<C: Component + std::fmt::Debug>(component: C);
+    #[system(component: C)]
+    #[add_to_schedule]
+    fn build_generic<C: Component + std::fmt::Debug>();
+
+    #[system(r#type: impl Into<String>, (min, max): (f32, f32))]
+    #[add_to_schedule]
+    fn r#move(_transforms: Query<&Transform>) {
+        info!("Move {} within {}..{}", r#type, min, max);
+    }
 }
 
 struct Cactus;
 
+#[derive(Resource)]
+struct CactusUpdatesEnabled(bool);
+
+#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+enum GameState {
+    #[default]
+    Playing,
+}
+
 impl Initializable for Cactus {
-    #[system]
+    #[system(once, fn_system)]
     fn init(_transforms: Query<&Transform>) {
         info!("Init!");
     }
 
     #[system_config]
     #[in_base_set(CoreSet::PostUpdate)]
-    #[before(apply_system_buffers)]
+    #[bevy_trait::before(apply_system_buffers)]
+    #[run_in_state(GameState::Playing)]
     fn init_config(_query: Query<&Transform>) {}
 
     #[system_app_config]
+    #[timed]
     fn init_app_config() {}
 
     #[system]
+    #[run_every(std::time::Duration::from_secs(1))]
     fn generic<C: Component>(_query: Query<&C>) {}
 
-    #[system(data: i32)]
+    #[system(toggleable(CactusUpdatesEnabled), data: i32)]
     fn needs_build(query: Query<&Transform, With<Visibility>>) {
         info!("Data: {:?}", data);
 
@@ -70,8 +90,16 @@ impl Initializable for Cactus {
 fn main() {
     let cactus_init = Cactus::init();
 
-    App::new()
-        .add_plugins(DefaultPlugins)
+    let mut app = App::new();
+
+    app.add_plugins(DefaultPlugins)
+        .add_state::<GameState>()
+        .insert_resource(CactusUpdatesEnabled(true))
         .add_system(cactus_init)
-        .run();
+        .add_system(Cactus::build_generic(Name::new("Rock")));
+
+    Cactus::add_needs_build_to(&mut app, CoreSchedule::Main, 42);
+    Cactus::add_build_generic_to(&mut app, CoreSchedule::Startup, Name::new("Cactus"));
+
+    app.run();
 }
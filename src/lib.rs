@@ -3,47 +3,189 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::must_use_candidate)]
 #![allow(clippy::redundant_closure_for_method_calls)]
+#![allow(clippy::doc_markdown)]
 #![doc = include_str!("../README.md")]
 
 use proc_macro::TokenStream;
+use proc_macro2::{
+    Delimiter,
+    Group,
+    TokenTree,
+};
 use quote::{
+    format_ident,
     quote,
+    quote_spanned,
     ToTokens,
 };
 use syn::{
+    braced,
+    bracketed,
+    ext::IdentExt,
+    parenthesized,
     parse::{
         Parse,
         ParseStream,
+        Parser,
     },
     parse_macro_input,
     parse_quote,
     punctuated::Punctuated,
-    token::Comma,
+    token::{
+        self,
+        Comma,
+    },
+    visit::Visit,
+    Attribute,
     Block,
+    DeriveInput,
+    Expr,
+    ExprClosure,
     FnArg,
+    GenericArgument,
+    GenericParam,
+    Generics,
+    Ident,
     ImplItem,
     ImplItemMethod,
     Item,
-    Meta,
+    LitStr,
+    Pat,
+    Path,
+    PathArguments,
+    PathSegment,
     ReturnType,
+    Signature,
     Stmt,
+    Token,
+    TraitItem,
+    TraitItemMethod,
+    Type,
+    TypeParamBound,
 };
 
+/// The longest diagnostic name Bevy accepts, see `#[timed]`.
+const MAX_DIAGNOSTIC_NAME_WIDTH: usize = 32;
+
 macro_rules! parse_system_input {
     ($i:ident) => {
         if let ImplItem::Method(input) = parse_macro_input!($i as ImplItem) {
             input
         } else {
-            panic!("this attribute macro only works on trait methods")
+            return syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "this attribute macro only works on trait methods",
+            )
+            .into_compile_error()
+            .into();
         }
     };
 }
 
-struct Args(Punctuated<FnArg, Comma>);
+/// The args of a system attribute: builder args mixed with flags, optionally preceded by generics
+/// used by the builder args.
+#[derive(Default)]
+struct Args {
+    generics: Generics,
+    inputs: Punctuated<FnArg, Comma>,
+    flags: Vec<Flag>,
+}
 
 impl Parse for Args {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        Ok(Self(Punctuated::parse_terminated(input)?))
+        let generics = input.parse()?;
+        let mut inputs = Punctuated::new();
+        let mut flags = Vec::new();
+
+        while !input.is_empty() {
+            if Flag::peek(input) {
+                flags.push(input.parse()?);
+            } else {
+                inputs.push(input.parse()?);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Comma>()?;
+        }
+
+        Ok(Self {
+            generics,
+            inputs,
+            flags,
+        })
+    }
+}
+
+/// A marker, written either as an attribute (`#[once]`) or in the args of a system attribute
+/// (`#[system(once)]`).
+struct Flag {
+    ident: Ident,
+    /// The parenthesized arguments of the flag, if any.
+    args: Option<proc_macro2::TokenStream>,
+}
+
+impl Flag {
+    /// Returns the flag of a marker attribute, or `None` if the attribute cannot be a marker.
+    fn from_attr(attr: &Attribute) -> Option<Self> {
+        Some(Self {
+            ident: marker_ident(attr)?.clone(),
+            args: (!attr.tokens.is_empty()).then(|| attr.tokens.clone()),
+        })
+    }
+
+    /// Returns the arguments of the flag, which are required.
+    fn args(&self) -> syn::Result<proc_macro2::TokenStream> {
+        self.args
+            .clone()
+            .and_then(|args| syn::parse2::<Group>(args).ok())
+            .filter(|group| group.delimiter() == Delimiter::Parenthesis)
+            .map(|group| group.stream())
+            .ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &self.ident,
+                    format!("expected an argument: `{}(...)`", self.ident),
+                )
+            })
+    }
+
+    /// Checks that the flag has no arguments.
+    fn no_args(&self) -> syn::Result<()> {
+        match &self.args {
+            Some(args) => Err(syn::Error::new_spanned(
+                args,
+                format!("`{}` does not take arguments", self.ident),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Flag {
+    /// Returns `true` if the input starts with a flag, i.e. an ident optionally followed by
+    /// parenthesized arguments, and then a comma or the end of the input.
+    fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+
+        fork.parse::<Ident>().is_ok()
+            && (!fork.peek(token::Paren) || fork.parse::<Group>().is_ok())
+            && (fork.is_empty() || fork.peek(Comma))
+    }
+}
+
+impl Parse for Flag {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+
+        let args = if input.peek(token::Paren) {
+            Some(input.parse::<Group>()?.into_token_stream())
+        } else {
+            None
+        };
+
+        Ok(Self { ident, args })
     }
 }
 
@@ -53,73 +195,2076 @@ macro_rules! parse_system_args {
     };
 }
 
-fn impl_system<F>(input: ImplItemMethod, args: Args, output: ReturnType, body: F) -> TokenStream
-where
-    F: FnOnce(&Punctuated<FnArg, Comma>, &Block) -> proc_macro2::TokenStream,
-{
-    let params = &input.sig.inputs;
-    let block = &input.block;
+/// Returns the name of a potential scheduling attribute.
+///
+/// Both the bare form (`#[before(...)]`) and the path form (`#[bevy_trait::before(...)]`) are
+/// recognized, so the attributes can be disambiguated from those of other crates. The path may
+/// lead to a re-export of this crate, e.g. `#[$crate::reexports::bevy_trait::before(...)]` in
+/// code generated by a `macro_rules!` macro.
+fn marker_ident(attr: &Attribute) -> Option<&Ident> {
+    let mut segments = attr.path.segments.iter().rev();
+    let ident = &segments.next()?.ident;
+
+    match segments.next() {
+        None if attr.path.leading_colon.is_none() => Some(ident),
+        Some(segment) if segment.ident == "bevy_trait" => Some(ident),
+        _ => None,
+    }
+}
+
+/// Returns the name of a system builder attribute, or `None` if the attribute is not one.
+fn builder_attr(attr: &Attribute) -> Option<String> {
+    let ident = marker_ident(attr)?.to_string();
+    matches!(&*ident, "system" | "system_config" | "system_app_config").then_some(ident)
+}
+
+/// A system builder attribute found among the attributes of a method.
+struct BuilderAttr {
+    /// The position of the attribute, or of the `#[cfg_attr(...)]` it is nested in.
+    index: usize,
+    name: String,
+    attr: Attribute,
+    /// The predicate of the `#[cfg_attr(predicate, ...)]` the attribute is nested in, if any.
+    cfg: Option<proc_macro2::TokenStream>,
+}
+
+impl BuilderAttr {
+    fn find(attrs: &[Attribute]) -> Option<Self> {
+        flatten_cfg_attrs(attrs)
+            .into_iter()
+            .find_map(|(index, attr, cfg)| {
+                Some(Self {
+                    index,
+                    name: builder_attr(&attr)?,
+                    attr,
+                    cfg,
+                })
+            })
+    }
+
+    /// Returns a marker attribute that applies under the same condition as the builder attribute.
+    fn marker(&self, marker: &proc_macro2::TokenStream) -> Attribute {
+        match &self.cfg {
+            Some(cfg) => parse_quote! { #[cfg_attr(#cfg, #marker)] },
+            None => parse_quote! { #[#marker] },
+        }
+    }
+
+    /// Parses the args of the attribute, e.g. `data: i32, once`.
+    fn args(&self) -> syn::Result<Args> {
+        if self.attr.tokens.is_empty() {
+            Ok(Args::default())
+        } else {
+            self.attr.parse_args()
+        }
+    }
+
+    /// Returns `true` if the marker `name` is applied to the builder method, either as an attribute
+    /// or as a flag.
+    fn has_marker(&self, attrs: &[Attribute], name: &str) -> bool {
+        let is_flag =
+            matches!(self.args(), Ok(args) if args.flags.iter().any(|flag| flag.ident == name));
+
+        is_flag
+            || flatten_cfg_attrs(attrs)
+                .iter()
+                .any(|(_, attr, _)| matches!(marker_ident(attr), Some(ident) if ident == name))
+    }
+
+    /// Returns `true` if the builder method can be called without args or generics.
+    fn is_plain(&self, method: &TraitItemMethod) -> bool {
+        let Ok(args) = self.args() else {
+            return false;
+        };
+
+        let has_generics = |generics: &Generics| {
+            generics
+                .params
+                .iter()
+                .any(|param| !matches!(param, GenericParam::Lifetime(_)))
+        };
+
+        args.inputs.is_empty()
+            && (method.default.is_some() || method.sig.inputs.is_empty())
+            && !has_generics(&args.generics)
+            && !has_generics(&method.sig.generics)
+    }
+}
+
+/// Flattens `#[cfg_attr(predicate, attr, ...)]` into the attributes it applies, so markers can be
+/// found whether or not they are conditional.
+///
+/// Each attribute is returned with its position and the predicate it is conditional on, if any.
+fn flatten_cfg_attrs(
+    attrs: &[Attribute],
+) -> Vec<(usize, Attribute, Option<proc_macro2::TokenStream>)> {
+    let mut flattened = Vec::new();
+
+    for (index, attr) in attrs.iter().enumerate() {
+        match cfg_attr(attr) {
+            Some((predicate, nested)) => flattened.extend(
+                nested
+                    .into_iter()
+                    .map(|nested| (index, nested, Some(predicate.clone()))),
+            ),
+            None => flattened.push((index, attr.clone(), None)),
+        }
+    }
+
+    flattened
+}
+
+/// Splits `#[cfg_attr(predicate, attr, ...)]` into its predicate and the attributes it applies.
+fn cfg_attr(attr: &Attribute) -> Option<(proc_macro2::TokenStream, Vec<Attribute>)> {
+    if !attr.path.is_ident("cfg_attr") {
+        return None;
+    }
+
+    let args = syn::parse2::<Group>(attr.tokens.clone()).ok()?.stream();
+    let mut chunks = vec![proc_macro2::TokenStream::new()];
+
+    for token in args {
+        match (&token, chunks.last_mut()) {
+            (TokenTree::Punct(punct), _) if punct.as_char() == ',' => {
+                chunks.push(proc_macro2::TokenStream::new());
+            }
+            (_, Some(chunk)) => chunk.extend([token]),
+            (_, None) => {}
+        }
+    }
+
+    let predicate = chunks.remove(0);
+
+    let attrs = chunks
+        .into_iter()
+        .filter(|chunk| !chunk.is_empty())
+        .filter_map(|chunk| Attribute::parse_outer.parse2(quote! { #[#chunk] }).ok())
+        .flatten()
+        .collect();
+
+    Some((predicate, attrs))
+}
+
+/// Checks that the system of a `#[fn_system]` method can be defined as a nested fn.
+///
+/// Nested fns cannot capture builder args, nor use the generics of the method or `Self`. The
+/// params and block are the ones written by the user, before they are wrapped.
+fn check_fn_system(
+    marker: &Ident,
+    sig: &Signature,
+    args: &Punctuated<FnArg, Comma>,
+    params: &Punctuated<FnArg, Comma>,
+    block: &Block,
+    timed: Option<&Ident>,
+) -> syn::Result<()> {
+    if let Some(timed) = timed {
+        return Err(syn::Error::new_spanned(
+            timed,
+            "`#[timed]` without a name records the run time under the name of `Self`, which the \
+             nested fn of `#[fn_system]` cannot use",
+        ));
+    }
+
+    let reason = if !args.is_empty() {
+        "it cannot capture builder args"
+    } else if !sig.generics.params.is_empty() {
+        "it cannot use the generics of the method"
+    } else if mentions_self(quote! { #params #block }) {
+        "it cannot use `Self`"
+    } else {
+        return Ok(());
+    };
+
+    Err(syn::Error::new_spanned(
+        marker,
+        format!("`#[fn_system]` defines the system as a nested fn, so {reason}"),
+    ))
+}
+
+fn mentions_self(tokens: proc_macro2::TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == "Self",
+        TokenTree::Group(group) => mentions_self(group.stream()),
+        _ => false,
+    })
+}
+
+/// Checks that a body using the `self` value has a receiver among the builder args.
+///
+/// Without one, the generated builder is not a method, and the compiler suggests adding the
+/// receiver to the signature, where it would be taken for a system param.
+fn check_receiver(args: &Punctuated<FnArg, Comma>, block: &Block) -> syn::Result<()> {
+    if args.iter().any(|arg| matches!(arg, FnArg::Receiver(_))) {
+        return Ok(());
+    }
+
+    let mut value = SelfValue(None);
+    value.visit_block(block);
+
+    match value.0 {
+        Some(value) => Err(syn::Error::new_spanned(
+            value,
+            "`self` is used in the body, but the builder does not take a receiver; declare it in \
+             the attribute args, e.g. `#[system(&self)]`",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Finds the first use of the `self` value in a body.
+///
+/// Nested items are skipped, since they have their own receivers, e.g. the `&self` of a method in
+/// a nested `impl`.
+struct SelfValue(Option<Ident>);
+
+impl<'ast> Visit<'ast> for SelfValue {
+    fn visit_expr_path(&mut self, expr: &'ast syn::ExprPath) {
+        if self.0.is_none() && expr.qself.is_none() && expr.path.is_ident("self") {
+            self.0 = expr.path.get_ident().cloned();
+        }
+    }
+
+    fn visit_item(&mut self, _: &'ast Item) {}
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if self.0.is_none() {
+            self.0 = self_value(mac.tokens.clone());
+        }
+    }
+}
+
+/// Returns the first `self` in the tokens of a macro call that is not the start of a path, e.g.
+/// `self::module`.
+fn self_value(tokens: proc_macro2::TokenStream) -> Option<Ident> {
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident)
+                if ident == "self"
+                    && !matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == ':') =>
+            {
+                return Some(ident);
+            }
+            TokenTree::Group(group) => {
+                if let Some(ident) = self_value(group.stream()) {
+                    return Some(ident);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Checks that the builder args can be captured by the system, which has to be `'static`.
+fn check_static(args: &Punctuated<FnArg, Comma>, errors: &mut Errors) {
+    for arg in args {
+        let FnArg::Typed(arg) = arg else {
+            continue;
+        };
+
+        if let Some(borrow) = borrow(&arg.ty) {
+            errors.push(syn::Error::new_spanned(
+                borrow,
+                "builder args are moved into the system, which must be `'static`, so they cannot \
+                 borrow; take an owned value or an `Arc` instead",
+            ));
+        }
+    }
+}
+
+/// Returns the part of a type that borrows with a lifetime other than `'static`, if any.
+///
+/// Lifetimes of `Fn(...)` sugar and fn pointers are not reported, since they do not make the type
+/// itself borrow.
+fn borrow(ty: &Type) -> Option<proc_macro2::TokenStream> {
+    let is_static = |lifetime: &syn::Lifetime| lifetime.ident == "static";
+
+    match ty {
+        Type::Reference(reference) => match &reference.lifetime {
+            Some(lifetime) if is_static(lifetime) => borrow(&reference.elem),
+            Some(lifetime) => Some(lifetime.to_token_stream()),
+            None => Some(reference.and_token.to_token_stream()),
+        },
+        Type::Path(path) => {
+            let qself = path.qself.as_ref().and_then(|qself| borrow(&qself.ty));
+            qself.or_else(|| path_borrow(&path.path))
+        }
+        Type::TraitObject(object) => bounds_borrow(object.bounds.iter()),
+        Type::ImplTrait(ty) => bounds_borrow(ty.bounds.iter()),
+        Type::Tuple(tuple) => tuple.elems.iter().find_map(borrow),
+        Type::Array(array) => borrow(&array.elem),
+        Type::Slice(slice) => borrow(&slice.elem),
+        Type::Paren(paren) => borrow(&paren.elem),
+        Type::Group(group) => borrow(&group.elem),
+        _ => None,
+    }
+}
+
+fn path_borrow(path: &Path) -> Option<proc_macro2::TokenStream> {
+    path.segments.iter().find_map(|segment| {
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Lifetime(lifetime) if lifetime.ident != "static" => {
+                Some(lifetime.to_token_stream())
+            }
+            GenericArgument::Type(ty) => borrow(ty),
+            _ => None,
+        })
+    })
+}
+
+fn bounds_borrow<'a>(
+    mut bounds: impl Iterator<Item = &'a TypeParamBound>,
+) -> Option<proc_macro2::TokenStream> {
+    bounds.find_map(|bound| match bound {
+        TypeParamBound::Lifetime(lifetime) if lifetime.ident != "static" => {
+            Some(lifetime.to_token_stream())
+        }
+        TypeParamBound::Trait(bound) => path_borrow(&bound.path),
+        TypeParamBound::Lifetime(_) => None,
+    })
+}
+
+/// Converts a builder arg of type `impl Into<T>` into a `T` before it is moved into the system.
+///
+/// The concrete type behind `impl Into<T>` is not known to be `Send + Sync + 'static`, so it
+/// cannot be captured as is.
+fn into_conversion(arg: &FnArg) -> Option<proc_macro2::TokenStream> {
+    let FnArg::Typed(arg) = arg else {
+        return None;
+    };
+
+    let (Pat::Ident(pat), Type::ImplTrait(ty)) = (&*arg.pat, &*arg.ty) else {
+        return None;
+    };
+
+    let target = ty.bounds.iter().find_map(|bound| {
+        let TypeParamBound::Trait(bound) = bound else {
+            return None;
+        };
+
+        let segment = bound.path.segments.last()?;
+
+        if segment.ident != "Into" {
+            return None;
+        }
+
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+
+        match args.args.first()? {
+            GenericArgument::Type(target) if args.args.len() == 1 => Some(target),
+            _ => None,
+        }
+    })?;
+
+    let ident = &pat.ident;
+    let cfgs = cfgs(&arg.attrs);
+
+    Some(quote! {
+        #(#cfgs)*
+        let #ident: #target = ::core::convert::Into::into(#ident);
+    })
+}
+
+/// Returns the `#[cfg(...)]` attributes of a builder arg, which also apply to the code that uses it.
+fn cfgs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path.is_ident("cfg"))
+}
+
+/// Returns the statements that prepare the builder args before they are moved into the system.
+fn captures(
+    args: &Punctuated<FnArg, Comma>,
+    weak: &[Ident],
+    errors: &mut Errors,
+) -> Vec<proc_macro2::TokenStream> {
+    let conversions = args.iter().filter_map(into_conversion);
+
+    let weak = weak.iter().filter_map(|ident| {
+        weak_capture(ident, args)
+            .map_err(|error| errors.push(error))
+            .ok()
+    });
+
+    conversions.chain(weak).collect()
+}
+
+/// Downgrades an `Arc` builder arg to a `Weak` before it is moved into the system.
+fn weak_capture(
+    ident: &Ident,
+    args: &Punctuated<FnArg, Comma>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let arg = args.iter().find_map(|arg| match arg {
+        FnArg::Typed(arg) if matches!(&*arg.pat, Pat::Ident(pat) if pat.ident == *ident) => {
+            Some(arg)
+        }
+        _ => None,
+    });
+
+    let Some(arg) = arg else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            format!("`{ident}` is not a builder arg of this method"),
+        ));
+    };
+
+    let cfgs = cfgs(&arg.attrs);
+
+    Ok(quote! {
+        #(#cfgs)*
+        let #ident = ::std::sync::Arc::downgrade(&#ident);
+    })
+}
+
+/// Returns the name of the type returned by a method, ignoring its path.
+fn return_type_ident(output: &ReturnType) -> Option<&Ident> {
+    if let ReturnType::Type(_, ty) = output {
+        if let Type::Path(path) = &**ty {
+            return path.path.segments.last().map(|segment| &segment.ident);
+        }
+    }
+
+    None
+}
+
+/// Returns the attribute that generates a builder with the given return type.
+fn attribute_for(return_type: &str) -> Option<&'static str> {
+    match return_type {
+        "BoxedSystem" => Some("system"),
+        "SystemConfig" => Some("system_config"),
+        "SystemAppConfig" => Some("system_app_config"),
+        _ => None,
+    }
+}
+
+/// The attributes of a builder method, sorted by what the expansion does with them.
+#[derive(Default)]
+struct Markers {
+    /// Attributes that are not recognized and are kept on the builder method.
+    attrs: Vec<Attribute>,
+    /// Calls applied to the built system, e.g. `before(...)`.
+    metadata: Vec<proc_macro2::TokenStream>,
+    wrapper: Wrapper,
+    /// `#[fn_system]`
+    fn_system: Option<Ident>,
+    /// `#[timed]` without a name, which names the diagnostic after `Self`.
+    timed: Option<Ident>,
+    /// Builder args captured as a `Weak` through `#[capture(weak(...))]`.
+    weak: Vec<Ident>,
+    helpers: Vec<Helper>,
+    /// Markers that can only be applied once, as they were applied.
+    applied: Vec<Ident>,
+}
+
+impl Markers {
+    /// Markers that can be applied more than once, since each application adds to the system.
+    const REPEATABLE: &'static [&'static str] = &[
+        "in_set",
+        "in_base_set",
+        "before",
+        "after",
+        "run_if",
+        "ambiguous_with",
+        "run_in_state",
+        "before_all_of",
+        "after_all_of",
+        "capture",
+    ];
+
+    /// `output` is the return type of the generated builder.
+    fn parse(
+        attrs: Vec<Attribute>,
+        flags: Vec<Flag>,
+        method: &Ident,
+        output: &ReturnType,
+        errors: &mut Errors,
+    ) -> Self {
+        let mut markers = Self::default();
+        let builder = return_type_ident(output)
+            .and_then(|ident| attribute_for(&ident.to_string()))
+            .unwrap_or_default();
+
+        for attr in attrs {
+            let Some(flag) = Flag::from_attr(&attr) else {
+                markers.attrs.push(attr);
+                continue;
+            };
+
+            match markers.push(flag, method, builder) {
+                Ok(true) => {}
+                Ok(false) => markers.attrs.push(attr),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        for flag in flags {
+            let ident = flag.ident.clone();
+
+            match markers.push(flag, method, builder) {
+                Ok(true) => {}
+                Ok(false) => errors.push(syn::Error::new_spanned(
+                    &ident,
+                    format!("unknown flag `{ident}`"),
+                )),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        // The scheduling attributes of a `#[with_config]` method are applied by its helper, so
+        // the builder itself stays untouched.
+        for helper in &mut markers.helpers {
+            if let Helper::Config(_, metadata) = helper {
+                *metadata = std::mem::take(&mut markers.metadata);
+            }
+        }
+
+        markers
+    }
+
+    /// Applies a flag, returning `false` if it is not a known marker.
+    fn push(&mut self, flag: Flag, method: &Ident, builder: &str) -> syn::Result<bool> {
+        let ident = flag.ident.clone();
+        let name = ident.to_string();
+
+        if !Self::REPEATABLE.contains(&&*name) && self.applied.contains(&ident) {
+            return Err(syn::Error::new_spanned(
+                &ident,
+                format!("`{name}` is applied more than once to this method"),
+            ));
+        }
+
+        match &*name {
+            "in_set" | "in_base_set" | "before" | "after" | "run_if" | "ambiguous_with"
+            | "in_schedule" => {
+                let value = flag.args()?;
+                self.metadata.push(quote! { #ident(#value) });
+            }
+            "run_in_state" => {
+                let state = flag.args()?;
+                self.metadata.push(quote! {
+                    run_if(bevy::ecs::schedule::common_conditions::in_state(#state))
+                });
+            }
+            "before_all_of" | "after_all_of" => {
+                let trait_ = syn::parse2::<Path>(flag.args()?)?;
+                let set = trait_set(&trait_, "Systems");
+                let order = format_ident!("{}", name.trim_end_matches("_all_of"));
+                self.metadata.push(quote! { #order(#set) });
+            }
+            "no_default_base_set" | "ambiguous_with_all" | "on_startup" => {
+                flag.no_args()?;
+                self.metadata.push(quote! { #ident() });
+            }
+            "once" => {
+                flag.no_args()?;
+                self.wrapper.once();
+            }
+            "run_every" => {
+                let duration = flag.args()?;
+                self.wrapper.run_every(&duration);
+            }
+            "toggleable" => {
+                let resource = flag.args()?;
+                self.wrapper.toggleable(&resource);
+            }
+            "fallible" => {
+                let error = flag.args.is_some().then(|| flag.args()).transpose()?;
+                self.wrapper.fallible(error);
+            }
+            "on_error" => {
+                let handler = flag.args()?;
+                self.wrapper.on_error(handler);
+            }
+            "timed" => self.timed(&flag, method)?,
+            "fn_system" => {
+                flag.no_args()?;
+                self.fn_system = Some(flag.ident);
+            }
+            "capture" => {
+                let captures = flag.args()?;
+                self.capture(captures)?;
+            }
+            "add_to_schedule" => {
+                flag.no_args()?;
+                self.helpers.push(Helper::Schedule(ident.span()));
+            }
+            "add_to_render_app" => {
+                flag.no_args()?;
+                self.helpers.push(Helper::RenderApp(ident.span()));
+            }
+            "apply_to_world" => {
+                flag.no_args()?;
+                self.helpers.push(Helper::World(ident.span()));
+            }
+            "with_config" => {
+                flag.no_args()?;
+                self.helpers.push(Helper::Config(ident.span(), Vec::new()));
+            }
+            "extract" => {
+                flag.no_args()?;
+
+                if builder != "system_app_config" {
+                    return Err(syn::Error::new_spanned(
+                        &ident,
+                        "`extract` is only supported on `#[system_app_config]`",
+                    ));
+                }
+
+                self.metadata
+                    .push(quote! { in_schedule(bevy::render::ExtractSchedule) });
+            }
+            _ => return Ok(false),
+        }
+
+        self.applied.push(ident);
+
+        Ok(true)
+    }
+
+    /// `#[timed]` or `#[timed("name")]`
+    fn timed(&mut self, flag: &Flag, method: &Ident) -> syn::Result<()> {
+        let name = flag
+            .args
+            .is_some()
+            .then(|| syn::parse2::<LitStr>(flag.args()?))
+            .transpose()?;
+
+        if let Some(name) = &name {
+            if name.value().chars().count() > MAX_DIAGNOSTIC_NAME_WIDTH {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    format!(
+                        "a diagnostic name is at most {MAX_DIAGNOSTIC_NAME_WIDTH} characters long"
+                    ),
+                ));
+            }
+        } else {
+            self.timed = Some(flag.ident.clone());
+        }
+
+        self.wrapper.timed(method, name.as_ref());
+        Ok(())
+    }
+
+    /// `#[capture(weak(arg, ...), ...)]`
+    fn capture(&mut self, captures: proc_macro2::TokenStream) -> syn::Result<()> {
+        let captures = Punctuated::<Flag, Comma>::parse_terminated.parse2(captures)?;
+
+        for capture in captures {
+            if capture.ident != "weak" {
+                return Err(syn::Error::new_spanned(
+                    &capture.ident,
+                    format!("unknown capture mode `{}`, expected `weak`", capture.ident),
+                ));
+            }
+
+            let args = capture.args()?;
+            let args = Punctuated::<Ident, Comma>::parse_terminated.parse2(args)?;
+
+            self.weak.extend(args);
+        }
+
+        Ok(())
+    }
+}
+
+/// System params and statements injected around the body of a system.
+#[derive(Default)]
+struct Wrapper {
+    params: Vec<(bool, Ident, proc_macro2::TokenStream)>,
+    prologue: Vec<proc_macro2::TokenStream>,
+    /// Statements run after the body, even if it returns early.
+    epilogue: Vec<proc_macro2::TokenStream>,
+    /// The error type of a `#[fallible]` body.
+    error: Option<proc_macro2::TokenStream>,
+    /// The system the errors of a fallible body are piped into, set by `#[on_error(handler)]`.
+    handler: Option<proc_macro2::TokenStream>,
+    /// The flag of `#[once]`, which is set right before the body.
+    once: Option<Ident>,
+}
+
+impl Wrapper {
+    /// Adds a system param and returns the ident it is bound to.
+    ///
+    /// The ident is hygienic, so it can neither collide with nor be referenced by the user's code.
+    fn param(&mut self, mutable: bool, name: &str, ty: proc_macro2::TokenStream) -> Ident {
+        let ident = Ident::new(name, proc_macro2::Span::mixed_site());
+        self.params.push((mutable, ident.clone(), ty));
+        ident
+    }
+
+    fn is_empty(&self) -> bool {
+        self.params.is_empty()
+            && self.prologue.is_empty()
+            && self.epilogue.is_empty()
+            && self.error.is_none()
+    }
+
+    /// Returns the return type of a fallible system and the system its output is piped into.
+    fn output(&self) -> Option<(proc_macro2::TokenStream, proc_macro2::TokenStream)> {
+        let error = self.error.as_ref()?;
+
+        let handler = self
+            .handler
+            .clone()
+            .unwrap_or_else(|| quote! { bevy::ecs::system::adapter::error });
+
+        Some((quote! { ::core::result::Result<(), #error> }, handler))
+    }
+
+    /// `#[once]`
+    fn once(&mut self) {
+        let once = self.param(true, "once", quote! { bevy::ecs::system::Local<bool> });
+
+        self.prologue.push(quote! {
+            if *#once {
+                return;
+            }
+        });
+
+        // The flag is only set once every other prologue let the body run, see `wrap`.
+        self.once = Some(once);
+    }
+
+    /// `#[run_every(duration)]`
+    fn run_every(&mut self, duration: &proc_macro2::TokenStream) {
+        let timer = self.param(
+            true,
+            "timer",
+            quote! { bevy::ecs::system::Local<::core::option::Option<bevy::time::Timer>> },
+        );
+        let time = self.param(
+            false,
+            "time",
+            quote! { bevy::ecs::system::Res<bevy::time::Time> },
+        );
+
+        self.prologue.push(quote! {
+            let #timer = #timer.get_or_insert_with(|| {
+                bevy::time::Timer::new(#duration, bevy::time::TimerMode::Repeating)
+            });
+
+            if !#timer.tick(#time.delta()).just_finished() {
+                return;
+            }
+        });
+    }
+
+    /// `#[toggleable(resource)]`
+    fn toggleable(&mut self, resource: &proc_macro2::TokenStream) {
+        let enabled = self.param(
+            false,
+            "enabled",
+            quote! { ::core::option::Option<bevy::ecs::system::Res<#resource>> },
+        );
+
+        // Spanned on the resource, so that a resource that does not wrap a `bool` is reported there.
+        let span = syn::spanned::Spanned::span(resource);
+        let value = Ident::new("value", proc_macro2::Span::mixed_site().located_at(span));
+        let is_enabled = quote_spanned! { span=>
+            ::core::convert::identity::<bool>(#value.0)
+        };
+
+        self.prologue.push(quote! {
+            if !#enabled.map_or(true, |#value| #is_enabled) {
+                return;
+            }
+        });
+    }
+
+    /// `#[timed]` or `#[timed("name")]`
+    fn timed(&mut self, method: &Ident, name: Option<&LitStr>) {
+        let method = method.unraw().to_string();
+
+        let diagnostic = self.param(true, "diagnostic", quote! {
+            bevy::ecs::system::Local<
+                ::core::option::Option<(bevy::diagnostic::DiagnosticId, ::std::sync::Arc<str>)>,
+            >
+        });
+        let commands = self.param(true, "commands", quote! { bevy::ecs::system::Commands });
+        let start = Ident::new("start", proc_macro2::Span::mixed_site());
+
+        // Without a name, the diagnostic is shown as `<type>::<method>` without the module path
+        // and generics of the type, cut to the width Bevy allows, but told apart by the full name.
+        let name = if let Some(name) = name {
+            quote! { (#name, ::std::string::ToString::to_string(#name)) }
+        } else {
+            quote! {{
+                let path = ::std::format!("{}::{}", ::core::any::type_name::<Self>(), #method);
+                let type_ = ::core::any::type_name::<Self>().split('<').next().unwrap_or_default();
+                let mut name = ::std::format!("{}::{}", type_.rsplit("::").next().unwrap_or_default(), #method);
+
+                if let ::core::option::Option::Some((end, _)) =
+                    name.char_indices().nth(#MAX_DIAGNOSTIC_NAME_WIDTH)
+                {
+                    name.truncate(end);
+                }
+
+                (path, name)
+            }}
+        };
+
+        self.prologue.push(quote! {
+            let #start = bevy::utils::Instant::now();
+        });
+
+        self.epilogue.push(quote! {
+            let elapsed = #start.elapsed().as_secs_f64() * 1000.0;
+
+            let (id, name) = ::core::clone::Clone::clone(#diagnostic.get_or_insert_with(|| {
+                use ::core::hash::{Hash as _, Hasher as _};
+
+                let (path, name) = #name;
+
+                let mut hasher = ::std::collections::hash_map::DefaultHasher::new();
+                path.hash(&mut hasher);
+                let id = bevy::diagnostic::DiagnosticId::from_u128(::core::convert::From::from(hasher.finish()));
+
+                (id, ::core::convert::From::from(name))
+            }));
+
+            // Recorded once the system buffers are applied, so that timed systems do not all
+            // conflict over `Diagnostics`.
+            #commands.add(move |world: &mut bevy::ecs::world::World| {
+                if let ::core::option::Option::Some(mut diagnostics) =
+                    world.get_resource_mut::<bevy::diagnostic::Diagnostics>()
+                {
+                    if diagnostics.get(id).is_none() {
+                        diagnostics.add(
+                            bevy::diagnostic::Diagnostic::new(id, ::std::string::ToString::to_string(&*name), 20)
+                                .with_suffix("ms"),
+                        );
+                    }
+
+                    diagnostics.add_measurement(id, || elapsed);
+                }
+            });
+        });
+    }
+
+    /// `#[fallible]` or `#[fallible(error)]`
+    fn fallible(&mut self, error: Option<proc_macro2::TokenStream>) {
+        self.error = Some(error.unwrap_or_else(Self::default_error));
+    }
+
+    /// `#[on_error(handler)]`, which implies `#[fallible]`.
+    fn on_error(&mut self, handler: proc_macro2::TokenStream) {
+        self.error.get_or_insert_with(Self::default_error);
+        self.handler = Some(handler);
+    }
+
+    fn default_error() -> proc_macro2::TokenStream {
+        quote! { ::std::boxed::Box<dyn ::std::error::Error> }
+    }
+
+    /// Appends the injected params to `params` as a single tuple and wraps `block`.
+    fn wrap(self, params: &mut Punctuated<FnArg, Comma>, block: Block) -> proc_macro2::TokenStream {
+        if self.is_empty() {
+            return block.into_token_stream();
+        }
+
+        if !self.params.is_empty() {
+            let pats = self.params.iter().map(|(mutable, ident, _)| {
+                let mutability = mutable.then(|| quote! { mut });
+                quote! { #mutability #ident }
+            });
+            let tys = self.params.iter().map(|(_, _, ty)| ty);
+
+            params.push(parse_quote! { (#(#pats,)*): (#(#tys,)*) });
+        }
+
+        let output = self.output();
+        let once = self.once.map(|once| quote! { *#once = true; });
+        let prologue = self.prologue.into_iter().chain(once);
+        let epilogue = self.epilogue;
+
+        if let Some((output, _)) = output {
+            let result = Ident::new("result", proc_macro2::Span::mixed_site());
+
+            quote! {
+                {
+                    let mut #result: #output = ::core::result::Result::Ok(());
+                    (|| {
+                        #(#prologue)*
+                        #result = (|| -> #output #block)();
+                        #(#epilogue)*
+                    })();
+                    #result
+                }
+            }
+        } else if epilogue.is_empty() {
+            quote! {
+                {
+                    #(#prologue)*
+                    #block
+                }
+            }
+        } else {
+            quote! {
+                {
+                    #(#prologue)*
+                    (|| #block)();
+                    #(#epilogue)*
+                }
+            }
+        }
+    }
+}
+
+/// Collects every error found during an expansion so they can be reported together.
+#[derive(Default)]
+struct Errors(Option<syn::Error>);
+
+impl Errors {
+    fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    fn into_compile_error(self) -> Option<proc_macro2::TokenStream> {
+        self.0.map(syn::Error::into_compile_error)
+    }
+}
+
+fn impl_system<F>(input: ImplItemMethod, args: Args, output: ReturnType, body: F) -> TokenStream
+where
+    F: FnOnce(proc_macro2::Span, proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+{
+    let ImplItemMethod {
+        attrs,
+        mut sig,
+        block,
+        ..
+    } = input;
+
+    let Args {
+        generics,
+        inputs: args,
+        flags,
+    } = args;
+
+    if !generics.params.is_empty() {
+        let mut params = std::mem::take(&mut sig.generics.params)
+            .into_iter()
+            .chain(generics.params)
+            .collect::<Vec<_>>();
+
+        // Lifetimes have to be declared before types and consts.
+        params.sort_by_key(|param| !matches!(param, GenericParam::Lifetime(_)));
+
+        sig.generics.params = params.into_iter().collect();
+    }
+
+    let mut errors = Errors::default();
+
+    let Markers {
+        attrs,
+        metadata,
+        wrapper,
+        fn_system,
+        weak,
+        helpers,
+        timed,
+        applied: _,
+    } = Markers::parse(attrs, flags, &sig.ident, &output, &mut errors);
+
+    let is_empty = matches!(block.stmts.first(), Some(Stmt::Item(Item::Verbatim(_))));
+
+    let body = if is_empty {
+        if !metadata.is_empty() || !wrapper.is_empty() || !weak.is_empty() {
+            errors.push(syn::Error::new_spanned(
+                &sig,
+                "scheduling, wrapping and capture attributes can only be applied to a method with a body",
+            ));
+        }
+
+        quote! { ; }
+    } else {
+        let mut params = std::mem::take(&mut sig.inputs)
+            .into_iter()
+            .filter(|param| match param {
+                FnArg::Receiver(receiver) => {
+                    errors.push(syn::Error::new_spanned(
+                        receiver,
+                        "a receiver is not a system parameter; pass it to the builder through the \
+                         attribute args instead, e.g. `#[system(&self)]`",
+                    ));
+                    false
+                }
+                FnArg::Typed(_) => true,
+            })
+            .collect();
+
+        if let Err(error) = check_receiver(&args, &block) {
+            errors.push(error);
+        }
+
+        let fn_system = fn_system.filter(|marker| {
+            check_fn_system(marker, &sig, &args, &params, &block, timed.as_ref())
+                .map_err(|error| errors.push(error))
+                .is_ok()
+        });
+
+        let output = wrapper.output();
+        let block = wrapper.wrap(&mut params, block);
+
+        let system = define_system(
+            &sig.ident,
+            fn_system.is_some().then_some(&*attrs),
+            &params,
+            output.as_ref(),
+            &block,
+        );
+
+        let body = configure(&body(sig.ident.span(), system), &metadata);
+        let captures = captures(&args, &weak, &mut errors);
+        check_static(&args, &mut errors);
+
+        quote! {
+            {
+                #(#captures)*
+                #body
+            }
+        }
+    };
+
+    if !is_empty || sig.inputs.is_empty() {
+        sig.inputs = args;
+    } else if !args.is_empty() {
+        errors.push(syn::Error::new_spanned(
+            &args,
+            "builder args are already declared in the signature of this method; declare them \
+             either in the attribute or in the signature, not both",
+        ));
+    }
+
+    if let Err(error) = check_return_type(&mut sig, &output) {
+        errors.push(error);
+    }
+
+    sig.output = output;
+
+    let helpers = helpers.iter().map(|helper| helper.expand(&sig));
+
+    let errors = errors.into_compile_error();
+
+    quote! {
+        #errors #(#attrs)* #sig #body #(#helpers)*
+    }
+    .into()
+}
+
+/// Defines the system, either as a closure or as a nested fn named after the method.
+///
+/// The generated tokens are spanned to the name of the method, so errors about the system as a
+/// whole, e.g. a param that is not a `SystemParam`, point at the method instead of the attribute.
+///
+/// A nested fn is passed the attributes of the builder method, so `#[inline]` and `#[cold]` also
+/// apply to the system. A fallible system is piped into its error handler, so the built system
+/// still returns `()`.
+fn define_system(
+    ident: &Ident,
+    fn_system: Option<&[Attribute]>,
+    params: &Punctuated<FnArg, Comma>,
+    output: Option<&(proc_macro2::TokenStream, proc_macro2::TokenStream)>,
+    block: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let system = if let Some(attrs) = fn_system {
+        let attrs = attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("inline") || attr.path.is_ident("cold"));
+        let output = output.map(|(output, _)| quote! { -> #output });
+
+        quote_spanned! { ident.span()=>
+            {
+                #(#attrs)*
+                fn #ident(#params) #output #block
+                #ident
+            }
+        }
+    } else {
+        quote_spanned! { ident.span()=> move |#params| #block }
+    };
+
+    let Some((_, handler)) = output else {
+        return system;
+    };
+
+    quote_spanned! { ident.span()=>
+        bevy::ecs::system::IntoPipeSystem::pipe(#system, #handler)
+    }
+}
+
+/// Applies the scheduling calls in `metadata` to `system`.
+///
+/// The calls are methods of `IntoSystemConfig` and `IntoSystemAppConfig`, which are brought into
+/// scope so the expansion does not rely on the user's imports.
+fn configure(
+    system: &proc_macro2::TokenStream,
+    metadata: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    if metadata.is_empty() {
+        return system.clone();
+    }
+
+    quote! {
+        {
+            use bevy::app::IntoSystemAppConfig as _;
+            use bevy::ecs::schedule::IntoSystemConfig as _;
+
+            #system #(.#metadata)*
+        }
+    }
+}
+
+/// Checks that a return type written on a builder method matches the generated one.
+///
+/// Builder methods cannot be `async` or `unsafe`, so these are removed from `sig` once reported.
+fn check_return_type(sig: &mut Signature, generated: &ReturnType) -> syn::Result<()> {
+    if let Some(asyncness) = sig.asyncness.take() {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "a system builder method cannot be `async`; the system it builds runs synchronously",
+        ));
+    }
+
+    if let Some(unsafety) = sig.unsafety.take() {
+        return Err(syn::Error::new_spanned(
+            unsafety,
+            "a system builder method cannot be `unsafe`; the system it builds is run by the \
+             schedule, where no caller upholds the safety contract of the method; use `unsafe` \
+             blocks in the body instead",
+        ));
+    }
+
+    let ReturnType::Type(_, ty) = &sig.output else {
+        return Ok(());
+    };
+
+    let declared = return_type_ident(&sig.output);
+    let generated = return_type_ident(generated);
+
+    if declared == generated {
+        return Ok(());
+    }
+
+    if matches!(declared, Some(declared) if declared == "Pin") {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "this method returns a future, likely because `#[async_trait]` rewrote it from an \
+             `async fn`; a system builder method cannot be `async`",
+        ));
+    }
+
+    let generated = generated.map(Ident::to_string).unwrap_or_default();
+
+    let suggestion = declared.and_then(|declared| {
+        attribute_for(&declared.to_string()).map(|attribute| (declared, attribute))
+    });
+
+    let message = match suggestion {
+        Some((declared, attribute)) => format!(
+            "the return type of this method is generated as `{generated}`; to build a \
+             `{declared}`, use `#[{attribute}]` instead"
+        ),
+        None => format!(
+            "the return type of this method is generated as `{generated}`; remove it from the \
+             signature"
+        ),
+    };
+
+    Err(syn::Error::new_spanned(ty, message))
+}
+
+/// Returns the type and const params of `generics` in declaration order, to pass them on in a
+/// turbofish.
+fn generic_args(generics: &Generics) -> impl Iterator<Item = &Ident> {
+    generics.params.iter().filter_map(|param| match param {
+        GenericParam::Type(param) => Some(&param.ident),
+        GenericParam::Const(param) => Some(&param.ident),
+        GenericParam::Lifetime(_) => None,
+    })
+}
+
+/// A provided method generated next to a builder method.
+enum Helper {
+    /// `#[add_to_schedule]`
+    Schedule(proc_macro2::Span),
+    /// `#[add_to_render_app]`
+    RenderApp(proc_macro2::Span),
+    /// `#[apply_to_world]`
+    World(proc_macro2::Span),
+    /// `#[with_config]`, with the scheduling attributes of the declaration.
+    Config(proc_macro2::Span, Vec<proc_macro2::TokenStream>),
+}
+
+impl Helper {
+    /// Checks that the helper can be generated for a builder method with the signature `sig`.
+    fn check(&self, sig: &Signature) -> syn::Result<()> {
+        let (marker, span) = match self {
+            Self::World(span) => ("apply_to_world", span),
+            Self::Config(span, _) => ("with_config", span),
+            Self::Schedule(_) | Self::RenderApp(_) => return Ok(()),
+        };
+
+        if matches!(return_type_ident(&sig.output), Some(ident) if ident == "BoxedSystem") {
+            return Ok(());
+        }
+
+        Err(syn::Error::new(
+            *span,
+            format!(
+                "`#[{marker}]` uses the built system directly, so it only works with `#[system]`"
+            ),
+        ))
+    }
+
+    /// Generates the helper, which uses the system built by the builder method `sig`.
+    fn expand(&self, sig: &Signature) -> proc_macro2::TokenStream {
+        let name = &sig.ident;
+        let unraw = name.unraw();
+
+        let (ident, span) = match self {
+            Self::Schedule(span) => (format!("add_{unraw}_to"), span),
+            Self::RenderApp(span) => (format!("add_{unraw}_to_render_app"), span),
+            Self::World(span) => (format!("apply_{unraw}_to"), span),
+            Self::Config(span, _) => (format!("{unraw}_config"), span),
+        };
+
+        if let Err(error) = self.check(sig) {
+            return error.into_compile_error();
+        }
+
+        let mut helper = sig.clone();
+        helper.ident = Ident::new(&ident, *span);
+        helper.output = ReturnType::Default;
+        helper.inputs.clear();
+
+        let mut receiver = None;
+        let mut args = Vec::<FnArg>::new();
+        let mut values = Vec::new();
+
+        for (i, arg) in sig.inputs.iter().enumerate() {
+            match arg {
+                FnArg::Receiver(arg) => receiver = Some(FnArg::Receiver(arg.clone())),
+                FnArg::Typed(arg) => {
+                    let value = match &*arg.pat {
+                        Pat::Ident(pat) => pat.ident.clone(),
+                        _ => format_ident!("arg{}", i),
+                    };
+                    let ty = &arg.ty;
+                    let cfgs = cfgs(&arg.attrs).collect::<Vec<_>>();
+
+                    args.push(parse_quote! { #(#cfgs)* #value: #ty });
+                    values.push(quote! { #(#cfgs)* #value });
+                }
+            }
+        }
+
+        let call = if receiver.is_some() {
+            quote! { self.#name }
+        } else {
+            quote! { Self::#name }
+        };
+
+        let generics = generic_args(&sig.generics);
+        let system = quote! { #call::<#(#generics),*>(#(#values),*) };
+
+        helper.inputs.extend(receiver);
+
+        match self {
+            Self::Schedule(_) | Self::RenderApp(_) => helper
+                .inputs
+                .push(parse_quote! { app: &mut bevy::app::App }),
+            Self::World(_) => helper
+                .inputs
+                .push(parse_quote! { world: &mut bevy::ecs::world::World }),
+            Self::Config(..) => {
+                helper.output = parse_quote! { -> bevy::ecs::schedule::SystemConfig };
+            }
+        }
+
+        let verb = if let Self::Config(..) = self {
+            "Configures"
+        } else {
+            "Adds"
+        };
+
+        let (doc, body) = match self {
+            Self::Schedule(_) => {
+                helper
+                    .inputs
+                    .push(parse_quote! { schedule: impl bevy::ecs::schedule::ScheduleLabel });
+
+                ("to `schedule`.", quote! {
+                    app.add_system(bevy::app::IntoSystemAppConfig::in_schedule(#system, schedule));
+                })
+            }
+            Self::RenderApp(_) => ("to the render sub-app.", quote! {
+                app.sub_app_mut(bevy::render::RenderApp).add_system(#system);
+            }),
+            Self::World(_) => (
+                "to `world`: runs it once, then applies its commands.",
+                quote! {
+                    let mut system = #system;
+                    bevy::ecs::system::System::initialize(&mut *system, world);
+                    bevy::ecs::system::System::run(&mut *system, (), world);
+                    bevy::ecs::system::System::apply_buffers(&mut *system, world);
+                },
+            ),
+            Self::Config(_, metadata) => (
+                "as a `SystemConfig`, with the scheduling attributes of its declaration.",
+                configure(
+                    &quote! { bevy::ecs::schedule::IntoSystemConfig::into_config(#system) },
+                    metadata,
+                ),
+            ),
+        };
+
+        helper.inputs.extend(args);
+
+        helper
+            .generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { Self: ::core::marker::Sized });
+
+        quote! {
+            #[doc = concat!(#verb, " the system built by [`Self::", stringify!(#name), "`] ", #doc)]
+            #helper {
+                #body
+            }
+        }
+    }
+}
+
+/// The input of `impl_system_for!`: `Type as Trait { method: |params| body, ... }`.
+struct ImplSystemFor {
+    ty: Type,
+    trait_: Path,
+    methods: Punctuated<SystemFn, Comma>,
+}
+
+impl Parse for ImplSystemFor {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse()?;
+        input.parse::<Token![as]>()?;
+        let trait_ = input.parse()?;
+
+        let content;
+        braced!(content in input);
+
+        Ok(Self {
+            ty,
+            trait_,
+            methods: content.parse_terminated(SystemFn::parse)?,
+        })
+    }
+}
+
+impl ToTokens for ImplSystemFor {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Self {
+            ty,
+            trait_,
+            methods,
+        } = self;
+
+        let methods = methods.iter();
+
+        tokens.extend(quote! {
+            impl #trait_ for #ty {
+                #(#methods)*
+            }
+        });
+    }
+}
+
+/// A method of `impl_system_for!`, defined by a closure: `#[attr] method: |params| body`.
+struct SystemFn {
+    attrs: Vec<Attribute>,
+    ident: Ident,
+    params: Punctuated<FnArg, Comma>,
+    block: Block,
+}
+
+impl Parse for SystemFn {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        let ident = input.call(Ident::parse_any)?;
+        input.parse::<Token![:]>()?;
+        let closure = input.parse::<ExprClosure>()?;
+
+        if let ReturnType::Type(_, ty) = &closure.output {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "the closure defines the body of a system, which cannot return a value",
+            ));
+        }
+
+        let params = closure
+            .inputs
+            .into_iter()
+            .map(|pat| match pat {
+                Pat::Type(pat) => Ok(FnArg::Typed(pat)),
+                pat => Err(syn::Error::new_spanned(
+                    pat,
+                    "system params need a type, e.g. `|query: Query<&Transform>|`",
+                )),
+            })
+            .collect::<syn::Result<_>>()?;
+
+        let block = match *closure.body {
+            Expr::Block(block) if block.label.is_none() => block.block,
+            body => parse_quote! { { #body } },
+        };
+
+        // The builder attribute has to come first, so it expands the other attributes.
+        match BuilderAttr::find(&attrs) {
+            Some(builder) => {
+                let attr = attrs.remove(builder.index);
+                attrs.insert(0, attr);
+            }
+            None => attrs.insert(0, parse_quote! { #[bevy_trait::system] }),
+        }
+
+        Ok(Self {
+            attrs,
+            ident,
+            params,
+            block,
+        })
+    }
+}
+
+impl ToTokens for SystemFn {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Self {
+            attrs,
+            ident,
+            params,
+            block,
+        } = self;
+
+        tokens.extend(quote! {
+            #(#attrs)*
+            fn #ident(#params) #block
+        });
+    }
+}
+
+/// The input of `expand_system!`: `(arg: T, ...) => |params| body`.
+struct ExpandSystem {
+    args: Punctuated<FnArg, Comma>,
+    closure: ExprClosure,
+}
+
+impl Parse for ExpandSystem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let args = content.parse_terminated::<FnArg, Comma>(FnArg::parse)?;
+        input.parse::<Token![=>]>()?;
+        let mut closure = input.parse::<ExprClosure>()?;
+
+        if let Some(FnArg::Receiver(receiver)) = args.first() {
+            return Err(syn::Error::new_spanned(
+                receiver,
+                "`expand_system!` builds a system outside of a trait, so it cannot take a receiver",
+            ));
+        }
+
+        if let ReturnType::Type(_, ty) = &closure.output {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "the closure defines the body of a system, which cannot return a value",
+            ));
+        }
+
+        // The system has to own the builder args it captures.
+        closure.capture.get_or_insert_with(Default::default);
+
+        Ok(Self { args, closure })
+    }
+}
+
+impl ToTokens for ExpandSystem {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Self { args, closure } = self;
+
+        tokens.extend(quote! {
+            move |#args| -> bevy::ecs::system::BoxedSystem {
+                ::std::boxed::Box::new(bevy::ecs::system::IntoSystem::into_system(#closure))
+            }
+        });
+    }
+}
+
+/// A system listed in `#[systems(...)]` or `#[impl_plugin(...)]`: `Trait::method` or
+/// `Trait::method(args)`, optionally followed by scheduling calls, e.g. `.in_schedule(...)`.
+struct SystemCall {
+    trait_: Path,
+    method: PathSegment,
+    args: Punctuated<Expr, Comma>,
+    /// The scheduling calls applied to the built system, e.g. `in_schedule(...)`.
+    metadata: Vec<proc_macro2::TokenStream>,
+}
+
+impl Parse for SystemCall {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse::<Path>()?;
+        let mut segments = path.segments.iter().cloned().collect::<Vec<_>>();
+
+        let method = match segments.pop() {
+            Some(method) if !segments.is_empty() => method,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &path,
+                    "expected a method of a trait: `Trait::method`",
+                ))
+            }
+        };
+
+        let trait_ = Path {
+            leading_colon: path.leading_colon,
+            segments: segments.into_iter().collect(),
+        };
+
+        let args = if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            content.parse_terminated(Expr::parse)?
+        } else {
+            Punctuated::new()
+        };
+
+        let mut metadata = Vec::new();
+
+        while input.peek(Token![.]) {
+            input.parse::<Token![.]>()?;
+
+            let ident = input.call(Ident::parse_any)?;
+            let args = input.parse::<Group>()?;
+
+            if args.delimiter() != Delimiter::Parenthesis {
+                return Err(syn::Error::new_spanned(args, "expected `(...)`"));
+            }
+
+            metadata.push(quote! { #ident #args });
+        }
+
+        Ok(Self {
+            trait_,
+            method,
+            args,
+            metadata,
+        })
+    }
+}
+
+impl ToTokens for SystemCall {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Self {
+            trait_,
+            method,
+            args,
+            metadata,
+        } = self;
+
+        let system = quote! { <Self as #trait_>::#method(#args) };
+
+        tokens.extend(configure(&system, metadata));
+    }
+}
+
+fn impl_systems(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let mut systems = Vec::new();
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("systems"))
+    {
+        systems.extend(attr.parse_args_with(Punctuated::<SystemCall, Comma>::parse_terminated)?);
+    }
+
+    if systems.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "list the systems to add with `#[systems(Trait::method, ...)]`",
+        ));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics bevy::app::IntoSystemAppConfigs<()> for #ident #ty_generics #where_clause {
+            fn into_app_configs(self) -> bevy::app::SystemAppConfigs {
+                bevy::app::IntoSystemAppConfigs::into_app_configs((#(#systems,)*))
+            }
+        }
+    })
+}
+
+fn impl_plugin_for(
+    systems: &Punctuated<SystemCall, Comma>,
+    input: &DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if systems.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "list the systems to add with `#[impl_plugin(Trait::method, ...)]`",
+        ));
+    }
+
+    let systems = systems.iter();
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #input
+
+        impl #impl_generics bevy::app::Plugin for #ident #ty_generics #where_clause {
+            fn build(&self, app: &mut bevy::app::App) {
+                #(app.add_system(#systems);)*
+            }
+        }
+    })
+}
+
+/// The input of `merge_trait_plugins!`: `Trait: [Type, ...], ...`.
+struct MergeTraitPlugins(Punctuated<TraitImplementers, Comma>);
+
+impl Parse for MergeTraitPlugins {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self(input.parse_terminated(TraitImplementers::parse)?))
+    }
+}
+
+impl ToTokens for MergeTraitPlugins {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let name = self
+            .0
+            .iter()
+            .map(TraitImplementers::name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let name = format!("merge_trait_plugins!({name})");
+        let traits = self.0.iter();
+
+        tokens.extend(quote! {
+            {
+                struct MergedTraitPlugins;
+
+                impl bevy::app::Plugin for MergedTraitPlugins {
+                    fn build(&self, app: &mut bevy::app::App) {
+                        #(#traits)*
+                    }
+
+                    fn name(&self) -> &str {
+                        #name
+                    }
+                }
+
+                MergedTraitPlugins
+            }
+        });
+    }
+}
+
+/// A trait of `merge_trait_plugins!` with its implementers: `Trait: [Type, ...]`.
+struct TraitImplementers {
+    trait_: Path,
+    types: Punctuated<Type, Comma>,
+}
+
+impl TraitImplementers {
+    fn name(&self) -> String {
+        let types = self
+            .types
+            .iter()
+            .map(|ty| ty.to_token_stream().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("{}: [{types}]", self.trait_.to_token_stream())
+    }
+}
+
+impl Parse for TraitImplementers {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let trait_ = input.parse()?;
+        input.parse::<Token![:]>()?;
+
+        let content;
+        bracketed!(content in input);
+
+        Ok(Self {
+            trait_,
+            types: content.parse_terminated(Type::parse)?,
+        })
+    }
+}
+
+impl ToTokens for TraitImplementers {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let trait_ = &self.trait_;
+
+        for ty in &self.types {
+            tokens.extend(quote! {
+                <#ty as #trait_>::add_all_systems_to(app);
+            });
+        }
+    }
+}
+
+/// The input of `systems_from_trait!`: `vis Trait`.
+struct SystemsFromTrait {
+    vis: syn::Visibility,
+    trait_: Path,
+}
+
+impl Parse for SystemsFromTrait {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            vis: input.parse()?,
+            trait_: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for SystemsFromTrait {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Self { vis, trait_ } = self;
+
+        let Some(segment) = trait_.segments.last() else {
+            return;
+        };
+
+        let ext = format_ident!("{}AppExt", segment.ident.unraw());
+        let method = format_ident!("add_{}", snake_case(&segment.ident.unraw().to_string()));
+
+        tokens.extend(quote! {
+            #[doc = concat!("Adds the systems of implementers of [`", stringify!(#trait_), "`] to an `App`.")]
+            #vis trait #ext {
+                #[doc = concat!("Adds the systems built by the builder methods of [`", stringify!(#trait_), "`] that take no args, as implemented by `T`.")]
+                fn #method<T: #trait_>(&mut self) -> &mut Self;
+            }
+
+            impl #ext for bevy::app::App {
+                fn #method<T: #trait_>(&mut self) -> &mut Self {
+                    <T as #trait_>::add_all_systems_to(self);
+                    self
+                }
+            }
+        });
+    }
+}
+
+/// Converts a CamelCase name to snake_case, e.g. `HasHealth` to `has_health`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+
+    for (index, char) in name.chars().enumerate() {
+        if char.is_uppercase() {
+            if index > 0 {
+                snake.push('_');
+            }
+
+            snake.extend(char.to_lowercase());
+        } else {
+            snake.push(char);
+        }
+    }
+
+    snake
+}
+
+/// Returns the path of a type generated for the trait at `trait_`, e.g. `path::InteractiveSet` for
+/// `path::Interactive<T>`.
+fn trait_set(trait_: &Path, suffix: &str) -> Path {
+    let mut set = trait_.clone();
+
+    if let Some(segment) = set.segments.last_mut() {
+        segment.ident = format_ident!("{}{suffix}", segment.ident.unraw());
+        segment.arguments = PathArguments::None;
+    }
+
+    set
+}
+
+/// Returns the variant of the system set of a trait that contains the systems of a method, if the
+/// method builds a system that can be placed in a set.
+fn set_variant(builder: &BuilderAttr, method: &Ident) -> Option<Ident> {
+    if !matches!(&*builder.name, "system_config" | "system_app_config") {
+        return None;
+    }
+
+    let variant = method
+        .unraw()
+        .to_string()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<String>();
+
+    Some(Ident::new(&variant, method.span()))
+}
+
+/// Checks that a method of a `#[system_set]` trait does not define `add_all_systems_to`, either
+/// itself or through the helper of `#[add_to_schedule]`.
+fn check_add_all_systems(method: &TraitItemMethod) -> syn::Result<()> {
+    let ident = &method.sig.ident;
+
+    let message = if ident == "add_all_systems_to" {
+        "`#[system_set]` provides `add_all_systems_to` on this trait; rename this method"
+    } else if ident.unraw() == "all_systems"
+        && matches!(BuilderAttr::find(&method.attrs), Some(builder) if builder.has_marker(&method.attrs, "add_to_schedule"))
+    {
+        "`#[add_to_schedule]` generates `add_all_systems_to` for this method, which \
+         `#[system_set]` already provides on this trait; rename this method"
+    } else {
+        return Ok(());
+    };
+
+    Err(syn::Error::new_spanned(ident, message))
+}
+
+fn impl_system_set(item: Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Trait(mut item) => {
+            let ident = &item.ident;
+            let set = format_ident!("{}Set", ident.unraw());
+            let all = format_ident!("{}Systems", ident.unraw());
+            let mut variants = Vec::new();
+            let mut systems = Vec::new();
+
+            for method in &mut item.items {
+                let TraitItem::Method(method) = method else {
+                    continue;
+                };
+
+                check_add_all_systems(method)?;
+
+                let Some(builder) = BuilderAttr::find(&method.attrs) else {
+                    continue;
+                };
+
+                let cfg = builder.cfg.as_ref().map(|cfg| quote! { #[cfg(#cfg)] });
+
+                if builder.is_plain(method) {
+                    let name = &method.sig.ident;
+                    systems.push(quote! { #cfg app.add_system(Self::#name()); });
+                }
+
+                let Some(variant) = set_variant(&builder, &method.sig.ident) else {
+                    continue;
+                };
+
+                if method.default.is_some() {
+                    method
+                        .attrs
+                        .push(builder.marker(&quote! { in_set(#set::#variant) }));
+                    method.attrs.push(builder.marker(&quote! { in_set(#all) }));
+                }
+
+                variants.push(quote! { #cfg #variant });
+            }
+
+            item.items.push(parse_quote! {
+                /// Adds the systems built by the builder methods of this trait that take no args
+                /// to `app`.
+                fn add_all_systems_to(app: &mut bevy::app::App)
+                where
+                    Self: ::core::marker::Sized,
+                {
+                    #(#systems)*
+                }
+            });
+
+            let ident = &item.ident;
+            let vis = &item.vis;
+
+            Ok(quote! {
+                #item
+
+                #[doc = concat!("The system sets of the systems built by [`", stringify!(#ident), "`].")]
+                #[derive(
+                    bevy::ecs::schedule::SystemSet,
+                    ::core::fmt::Debug,
+                    ::core::clone::Clone,
+                    ::core::cmp::PartialEq,
+                    ::core::cmp::Eq,
+                    ::core::hash::Hash,
+                )]
+                #vis enum #set {
+                    #(#variants,)*
+                }
+
+                #[doc = concat!("The system set of every system built by [`", stringify!(#ident), "`].")]
+                #[derive(
+                    bevy::ecs::schedule::SystemSet,
+                    ::core::fmt::Debug,
+                    ::core::clone::Clone,
+                    ::core::cmp::PartialEq,
+                    ::core::cmp::Eq,
+                    ::core::hash::Hash,
+                )]
+                #vis struct #all;
+            })
+        }
+        Item::Impl(mut item) => {
+            let Some((_, trait_, _)) = &item.trait_ else {
+                return Err(syn::Error::new_spanned(
+                    item.self_ty,
+                    "`#[system_set]` only works on impls of a trait",
+                ));
+            };
+
+            let set = trait_set(trait_, "Set");
+            let all = trait_set(trait_, "Systems");
+
+            for method in &mut item.items {
+                let ImplItem::Method(method) = method else {
+                    continue;
+                };
+
+                let Some(builder) = BuilderAttr::find(&method.attrs) else {
+                    continue;
+                };
+
+                if let Some(variant) = set_variant(&builder, &method.sig.ident) {
+                    method
+                        .attrs
+                        .push(builder.marker(&quote! { in_set(#set::#variant) }));
+                    method.attrs.push(builder.marker(&quote! { in_set(#all) }));
+                }
+            }
+
+            Ok(item.into_token_stream())
+        }
+        item => Err(syn::Error::new_spanned(
+            item,
+            "`#[system_set]` only works on traits and their impls",
+        )),
+    }
+}
 
-    let mut is_empty = false;
+fn impl_system_schedule(item: Item) -> syn::Result<proc_macro2::TokenStream> {
+    let Item::Trait(item) = item else {
+        return Err(syn::Error::new_spanned(
+            item,
+            "`#[system_schedule]` only works on traits",
+        ));
+    };
 
-    let mut attrs = Vec::new();
+    let ident = &item.ident;
+    let vis = &item.vis;
+    let schedule = format_ident!("{}Schedule", ident.unraw());
 
-    let body = if let Some(Stmt::Item(Item::Verbatim(item))) = block.stmts.get(0) {
-        is_empty = true;
-        item.clone()
-    } else {
-        let mut body = body(params, block);
+    Ok(quote! {
+        #item
 
-        for attr in input.attrs {
-            let meta = attr.parse_meta().unwrap();
-            let ident = meta.path().get_ident().unwrap();
-            let tokens = ident.into_token_stream();
-            let name = ident.to_string();
+        #[doc = concat!("A schedule for the systems built by [`", stringify!(#ident), "`], run by the user.")]
+        #[derive(
+            bevy::ecs::schedule::ScheduleLabel,
+            ::core::fmt::Debug,
+            ::core::clone::Clone,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::core::hash::Hash,
+        )]
+        #vis struct #schedule;
 
-            let value = if let Meta::List(list) = meta {
-                Some(list.nested.into_token_stream())
-            } else {
-                None
-            };
+        impl #schedule {
+            /// Adds a system to this schedule, creating the schedule if needed.
+            #vis fn add_system<M>(app: &mut bevy::app::App, system: impl bevy::app::IntoSystemAppConfig<M>) {
+                app.init_schedule(Self)
+                    .add_system(bevy::app::IntoSystemAppConfig::in_schedule(system, Self));
+            }
 
-            match &*name {
-                "in_set" | "in_base_set" | "before" | "after" | "run_if" | "ambiguous_with"
-                | "in_schedule" => {
-                    body = quote! { #body.#tokens(#value) };
-                }
-                "no_default_base_set" | "ambiguous_with_all" | "on_startup" => {
-                    body = quote! { #body.#tokens() };
-                }
-                _ => {
-                    attrs.push(attr);
-                }
+            /// Runs this schedule on `world`.
+            ///
+            /// Panics if no system was added to this schedule.
+            #vis fn run(world: &mut bevy::ecs::world::World) {
+                world.run_schedule(Self);
             }
         }
+    })
+}
 
-        quote! { { #body } }
-    };
+/// Adds `#[fallible]` to every builder method with a body that does not set its error handling.
+fn impl_fallible_systems(item: Item) -> syn::Result<proc_macro2::TokenStream> {
+    fn mark(attrs: &mut Vec<Attribute>) {
+        let Some(builder) = BuilderAttr::find(attrs) else {
+            return;
+        };
 
-    let mut sig = input.sig;
+        let is_fallible =
+            builder.has_marker(attrs, "fallible") || builder.has_marker(attrs, "on_error");
 
-    if !is_empty {
-        sig.inputs = args.0;
+        if !is_fallible {
+            attrs.push(builder.marker(&quote! { fallible }));
+        }
     }
 
-    sig.output = output;
+    match item {
+        Item::Trait(mut item) => {
+            for method in &mut item.items {
+                if let TraitItem::Method(method) = method {
+                    if method.default.is_some() {
+                        mark(&mut method.attrs);
+                    }
+                }
+            }
 
-    quote! {
-        #(#attrs)* #sig #body
+            Ok(item.into_token_stream())
+        }
+        Item::Impl(mut item) => {
+            for method in &mut item.items {
+                if let ImplItem::Method(method) = method {
+                    mark(&mut method.attrs);
+                }
+            }
+
+            Ok(item.into_token_stream())
+        }
+        item => Err(syn::Error::new_spanned(
+            item,
+            "`#[fallible_systems]` only works on traits and their impls",
+        )),
     }
-    .into()
 }
 
 /// Attribute to turn a method of a Trait into a `BoxedSystem`.
 ///
 /// Use this attribute when you want to prevent the implementer of your trait from defining scheduling metadata.
+///
+/// The return type of the builder method is always `BoxedSystem`, so it can be left out of the signature.
 /// ### `#[system]`
 /// Defines a system builder method without parameters.
 /// ### `#[system(arg: T, ...)]`
 /// Add args to the macro to add parameters to the builder method.
+/// On a trait declaration without a body, the args may instead be written in the signature itself.
+/// Args of type `impl Into<T>` are converted into `T` before they are moved into the system.
+/// Args can be gated with `#[cfg(...)]`, e.g. `#[system(#[cfg(debug_assertions)] label: String)]`; the body has to gate its uses of them too.
+/// Generics used only by the args can be declared before them, e.g. `<F: Fn() + Send + Sync + 'static> factory: F`.
+/// ### `#[system(flag, ...)]`
+/// The attributes listed below may also be passed to the macro as flags, mixed with the args, e.g. `#[system(once, data: i32)]`.
 /// # Examples
 /// ```
 /// trait Interactive {
@@ -191,6 +2336,41 @@ where
 ///         .run();
 /// }
 /// ```
+/// # Attributes
+/// Add any of these attributes alongside `#[system]`.
+/// Other attribute macros, e.g. `#[tracing::instrument]`, apply to the body of the system when written before `#[system]`, and to the builder method when written after it.
+/// <br/>&nbsp;
+/// ## Wrappers
+/// Add any of these attributes to a method with a body to change how the body runs.
+/// <br/>&nbsp;
+/// ### &ensp; attr `#[once]`
+/// &emsp;&emsp; *Runs the body only the first time the system runs.*
+/// ### &ensp; attr `#[run_every(duration: Duration)]`
+/// &emsp;&emsp; *Runs the body only once every `duration`, measured with `Res<Time>`.*
+/// ### &ensp; attr `#[toggleable(resource: Resource)]`
+/// &emsp;&emsp; *Skips the body while `resource` (a tuple struct wrapping a `bool`) is `false`; runs if the resource is absent.*
+/// ### &ensp; attr `#[timed(name: &str)]`
+/// &emsp;&emsp; *Records the run time of the body in `Diagnostics`, under `name`, which is at most 32 characters. `name` defaults to `<implementer>::<method>`, cut to 32 characters.*
+/// ### &ensp; attr `#[fallible(error: Type)]`
+/// &emsp;&emsp; *Lets the body return `Result<(), error>`, so it can use `?`. Errors are logged with Bevy's `error` adapter. `error` defaults to `Box<dyn Error>`.*
+/// ### &ensp; attr `#[on_error(handler: System)]`
+/// &emsp;&emsp; *Pipes the `Result` of a fallible body into `handler`, which takes it as `In<Result<(), error>>`, instead of logging errors. Implies `#[fallible]`.*
+/// ### &ensp; attr `#[fn_system]`
+/// &emsp;&emsp; *Defines the system as a nested fn instead of a closure, so it is named after the method. The method cannot have builder args or generics, and its body cannot use `Self`, so it cannot be `#[timed]` without a name either. `#[inline]` and `#[cold]` on the method also apply to the nested fn.*
+/// ### &ensp; attr `#[capture(weak(arg, ...))]`
+/// &emsp;&emsp; *Downgrades the `Arc<T>` builder args `arg, ...` to `Weak<T>` before they are moved into the system, so the system does not keep them alive.*
+/// <br/>&nbsp;<br/>&nbsp;
+/// ## Helpers
+/// Add any of these attributes to a method of the trait declaration to generate additional provided methods.
+/// <br/>&nbsp;
+/// ### &ensp; attr `#[add_to_schedule]`
+/// &emsp;&emsp; *Generates `add_<name>_to(app: &mut App, schedule: impl ScheduleLabel, arg: T, ...)`, which builds the system and adds it to `schedule`.*
+/// ### &ensp; attr `#[add_to_render_app]`
+/// &emsp;&emsp; *Generates `add_<name>_to_render_app(app: &mut App, arg: T, ...)`, which builds the system and adds it to the `RenderApp` sub-app. Requires the `bevy_render` feature of `bevy`.*
+/// ### &ensp; attr `#[apply_to_world]`
+/// &emsp;&emsp; *Generates `apply_<name>_to(world: &mut World, arg: T, ...)`, which builds the system, runs it once on `world` and applies its commands. Useful for methods that only spawn or insert through `Commands`.*
+/// ### &ensp; attr `#[with_config]`
+/// &emsp;&emsp; *Generates `<name>_config(arg: T, ...) -> SystemConfig`, which builds the system and applies the scheduling attributes written on the declaration, e.g. `#[before(...)]`. The builder method itself keeps returning the plain `BoxedSystem`.*
 #[proc_macro_attribute]
 pub fn system(args: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_system_input!(input);
@@ -200,8 +2380,8 @@ pub fn system(args: TokenStream, input: TokenStream) -> TokenStream {
         input,
         args,
         parse_quote! { -> bevy::ecs::system::BoxedSystem },
-        |params, system| {
-            quote! { Box::new(bevy::ecs::system::IntoSystem::into_system(move |#params| #system)) }
+        |span, system| {
+            quote_spanned! { span=> ::std::boxed::Box::new(bevy::ecs::system::IntoSystem::into_system(#system)) }
         },
     )
 }
@@ -209,10 +2389,18 @@ pub fn system(args: TokenStream, input: TokenStream) -> TokenStream {
 /// Attribute to turn a method of a Trait into a`SystemConfig`.
 ///
 /// Use this attribute when you want the implementer of your trait to be able to define scheduling metadata.
+///
+/// The return type of the builder method is always `SystemConfig`, so it can be left out of the signature.
 /// ### `#[system_config]`
 /// Defines a system builder method without parameters.
 /// ### `#[system_config(arg: T, ...)]`
 /// Add args to the macro to add parameters to the builder method.
+/// On a trait declaration without a body, the args may instead be written in the signature itself.
+/// Args of type `impl Into<T>` are converted into `T` before they are moved into the system.
+/// Args can be gated with `#[cfg(...)]`, e.g. `#[system_config(#[cfg(debug_assertions)] label: String)]`; the body has to gate its uses of them too.
+/// Generics used only by the args can be declared before them, e.g. `<F: Fn() + Send + Sync + 'static> factory: F`.
+/// ### `#[system_config(flag, ...)]`
+/// The attributes listed below may also be passed to the macro as flags, mixed with the args, e.g. `#[system_config(once, data: i32)]`.
 /// # Examples
 /// ```
 /// trait Interactive {
@@ -290,6 +2478,10 @@ pub fn system(args: TokenStream, input: TokenStream) -> TokenStream {
 /// ```
 /// # Attributes
 /// Add any of these attributes alongside `#[system_config]` to define schedule metadata for the system.
+/// Other attribute macros, e.g. `#[tracing::instrument]`, apply to the body of the system when written before `#[system_config]`, and to the builder method when written after it.
+///
+/// Each attribute may also be written with the crate path (`#[bevy_trait::before(...)]`) to avoid
+/// collisions with attributes of other crates.
 /// <br/>&nbsp;
 /// ##  Trait `IntoSystemConfig`
 /// Types that can be converted into a `SystemConfig`.
@@ -304,13 +2496,44 @@ pub fn system(args: TokenStream, input: TokenStream) -> TokenStream {
 /// &emsp;&emsp; *See `IntoSystemConfig::before`.*
 /// ### &ensp; attr `#[after(set: impl IntoSystemSet<_>)]`
 /// &emsp;&emsp; *See `IntoSystemConfig::after`.*
+/// ### &ensp; attr `#[before_all_of(Trait)]`, `#[after_all_of(Trait)]`
+/// &emsp;&emsp; *Orders the system before or after every system built by `Trait`, which must be marked with `#[system_set]`.*
 /// ### &ensp; attr `#[run_if(condition: impl Condition<_>)]`
 /// &emsp;&emsp; *See `IntoSystemConfig::run_if`.*
+/// ### &ensp; attr `#[run_in_state(state: impl States)]`
+/// &emsp;&emsp; *Shorthand for `#[run_if(in_state(state))]`.*
 /// ### &ensp; attr `#[ambiguous_with(set: impl IntoSystemSet<_>)]`
 /// &emsp;&emsp; *See `IntoSystemConfig::ambiguous_with`.*
 /// ### &ensp; attr `#[ambiguous_with_all]`
 /// &emsp;&emsp; *See `IntoSystemConfig::ambiguous_with_all`.*
 /// <br/>&nbsp;<br/>&nbsp;
+/// ## Wrappers
+/// Add any of these attributes to a method with a body to change how the body runs.
+/// <br/>&nbsp;
+/// ### &ensp; attr `#[once]`
+/// &emsp;&emsp; *Runs the body only the first time the system runs.*
+/// ### &ensp; attr `#[run_every(duration: Duration)]`
+/// &emsp;&emsp; *Runs the body only once every `duration`, measured with `Res<Time>`.*
+/// ### &ensp; attr `#[toggleable(resource: Resource)]`
+/// &emsp;&emsp; *Skips the body while `resource` (a tuple struct wrapping a `bool`) is `false`; runs if the resource is absent.*
+/// ### &ensp; attr `#[timed(name: &str)]`
+/// &emsp;&emsp; *Records the run time of the body in `Diagnostics`, under `name`, which is at most 32 characters. `name` defaults to `<implementer>::<method>`, cut to 32 characters.*
+/// ### &ensp; attr `#[fallible(error: Type)]`
+/// &emsp;&emsp; *Lets the body return `Result<(), error>`, so it can use `?`. Errors are logged with Bevy's `error` adapter. `error` defaults to `Box<dyn Error>`.*
+/// ### &ensp; attr `#[on_error(handler: System)]`
+/// &emsp;&emsp; *Pipes the `Result` of a fallible body into `handler`, which takes it as `In<Result<(), error>>`, instead of logging errors. Implies `#[fallible]`.*
+/// ### &ensp; attr `#[fn_system]`
+/// &emsp;&emsp; *Defines the system as a nested fn instead of a closure, so it is named after the method. The method cannot have builder args or generics, and its body cannot use `Self`, so it cannot be `#[timed]` without a name either. `#[inline]` and `#[cold]` on the method also apply to the nested fn.*
+/// ### &ensp; attr `#[capture(weak(arg, ...))]`
+/// &emsp;&emsp; *Downgrades the `Arc<T>` builder args `arg, ...` to `Weak<T>` before they are moved into the system, so the system does not keep them alive.*
+/// <br/>&nbsp;<br/>&nbsp;
+/// ## Helpers
+/// Add any of these attributes to a method of the trait declaration to generate additional provided methods.
+/// <br/>&nbsp;
+/// ### &ensp; attr `#[add_to_schedule]`
+/// &emsp;&emsp; *Generates `add_<name>_to(app: &mut App, schedule: impl ScheduleLabel, arg: T, ...)`, which builds the system and adds it to `schedule`.*
+/// ### &ensp; attr `#[add_to_render_app]`
+/// &emsp;&emsp; *Generates `add_<name>_to_render_app(app: &mut App, arg: T, ...)`, which builds the system and adds it to the `RenderApp` sub-app. Requires the `bevy_render` feature of `bevy`.*
 #[proc_macro_attribute]
 pub fn system_config(args: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_system_input!(input);
@@ -320,8 +2543,8 @@ pub fn system_config(args: TokenStream, input: TokenStream) -> TokenStream {
         input,
         args,
         parse_quote! { -> bevy::ecs::schedule::SystemConfig },
-        |params, system| {
-            quote! { (move |#params| #system).into_config() }
+        |span, system| {
+            quote_spanned! { span=> bevy::ecs::schedule::IntoSystemConfig::into_config(#system) }
         },
     )
 }
@@ -329,10 +2552,18 @@ pub fn system_config(args: TokenStream, input: TokenStream) -> TokenStream {
 /// Attribute to turn a method of a Trait into a `SystemAppConfig`.
 ///
 /// Use this attribute when you want the implementer of your trait to be able to define App-aware scheduling metadata.\
+///
+/// The return type of the builder method is always `SystemAppConfig`, so it can be left out of the signature.
 /// ### `#[system_app_config]`
 /// Defines a system builder method without parameters.
 /// ### `#[system_app_config(arg: T, ...)]`
 /// Add args to the macro to add parameters to the builder method.
+/// On a trait declaration without a body, the args may instead be written in the signature itself.
+/// Args of type `impl Into<T>` are converted into `T` before they are moved into the system.
+/// Args can be gated with `#[cfg(...)]`, e.g. `#[system_app_config(#[cfg(debug_assertions)] label: String)]`; the body has to gate its uses of them too.
+/// Generics used only by the args can be declared before them, e.g. `<F: Fn() + Send + Sync + 'static> factory: F`.
+/// ### `#[system_app_config(flag, ...)]`
+/// The attributes listed below may also be passed to the macro as flags, mixed with the args, e.g. `#[system_app_config(once, data: i32)]`.
 /// # Example
 /// ```
 /// trait Interactive {
@@ -412,6 +2643,10 @@ pub fn system_config(args: TokenStream, input: TokenStream) -> TokenStream {
 /// ```
 /// # Attributes
 /// Add any of these attributes alongside `#[system_app_config]` to define schedule metadata for the system.
+/// Other attribute macros, e.g. `#[tracing::instrument]`, apply to the body of the system when written before `#[system_app_config]`, and to the builder method when written after it.
+///
+/// Each attribute may also be written with the crate path (`#[bevy_trait::before(...)]`) to avoid
+/// collisions with attributes of other crates.
 /// <br/>&nbsp;
 /// ##  Trait `IntoSystemConfig`
 /// Types that can be converted into a `SystemConfig`.
@@ -426,8 +2661,12 @@ pub fn system_config(args: TokenStream, input: TokenStream) -> TokenStream {
 /// &emsp;&emsp; *See `IntoSystemConfig::before`.*
 /// ### &ensp; attr `#[after(set: impl IntoSystemSet<_>)]`
 /// &emsp;&emsp; *See `IntoSystemConfig::after`.*
+/// ### &ensp; attr `#[before_all_of(Trait)]`, `#[after_all_of(Trait)]`
+/// &emsp;&emsp; *Orders the system before or after every system built by `Trait`, which must be marked with `#[system_set]`.*
 /// ### &ensp; attr `#[run_if(condition: impl Condition<_>)]`
 /// &emsp;&emsp; *See `IntoSystemConfig::run_if`.*
+/// ### &ensp; attr `#[run_in_state(state: impl States)]`
+/// &emsp;&emsp; *Shorthand for `#[run_if(in_state(state))]`.*
 /// ### &ensp; attr `#[ambiguous_with(set: impl IntoSystemSet<_>)]`
 /// &emsp;&emsp; *See `IntoSystemConfig::ambiguous_with`.*
 /// ### &ensp; attr `#[ambiguous_with_all]`
@@ -440,6 +2679,37 @@ pub fn system_config(args: TokenStream, input: TokenStream) -> TokenStream {
 /// &emsp;&emsp; *See `IntoSystemAppConfig::in_schedule`.*
 /// ### &ensp; attr `#[on_startup]`
 /// &emsp;&emsp; *See `IntoSystemAppConfig::on_startup`.*
+/// ### &ensp; attr `#[extract]`
+/// &emsp;&emsp; *Shorthand for `#[in_schedule(ExtractSchedule)]`, to extract data into the render world. Requires the `bevy_render` feature of `bevy`.*
+/// <br/>&nbsp;<br/>&nbsp;
+/// ## Wrappers
+/// Add any of these attributes to a method with a body to change how the body runs.
+/// <br/>&nbsp;
+/// ### &ensp; attr `#[once]`
+/// &emsp;&emsp; *Runs the body only the first time the system runs.*
+/// ### &ensp; attr `#[run_every(duration: Duration)]`
+/// &emsp;&emsp; *Runs the body only once every `duration`, measured with `Res<Time>`.*
+/// ### &ensp; attr `#[toggleable(resource: Resource)]`
+/// &emsp;&emsp; *Skips the body while `resource` (a tuple struct wrapping a `bool`) is `false`; runs if the resource is absent.*
+/// ### &ensp; attr `#[timed(name: &str)]`
+/// &emsp;&emsp; *Records the run time of the body in `Diagnostics`, under `name`, which is at most 32 characters. `name` defaults to `<implementer>::<method>`, cut to 32 characters.*
+/// ### &ensp; attr `#[fallible(error: Type)]`
+/// &emsp;&emsp; *Lets the body return `Result<(), error>`, so it can use `?`. Errors are logged with Bevy's `error` adapter. `error` defaults to `Box<dyn Error>`.*
+/// ### &ensp; attr `#[on_error(handler: System)]`
+/// &emsp;&emsp; *Pipes the `Result` of a fallible body into `handler`, which takes it as `In<Result<(), error>>`, instead of logging errors. Implies `#[fallible]`.*
+/// ### &ensp; attr `#[fn_system]`
+/// &emsp;&emsp; *Defines the system as a nested fn instead of a closure, so it is named after the method. The method cannot have builder args or generics, and its body cannot use `Self`, so it cannot be `#[timed]` without a name either. `#[inline]` and `#[cold]` on the method also apply to the nested fn.*
+/// ### &ensp; attr `#[capture(weak(arg, ...))]`
+/// &emsp;&emsp; *Downgrades the `Arc<T>` builder args `arg, ...` to `Weak<T>` before they are moved into the system, so the system does not keep them alive.*
+/// <br/>&nbsp;<br/>&nbsp;
+/// ## Helpers
+/// Add any of these attributes to a method of the trait declaration to generate additional provided methods.
+/// <br/>&nbsp;
+/// ### &ensp; attr `#[add_to_schedule]`
+/// &emsp;&emsp; *Generates `add_<name>_to(app: &mut App, schedule: impl ScheduleLabel, arg: T, ...)`, which builds the system and adds it to `schedule`.*
+/// &emsp;&emsp; *Panics if the implementer already placed the system in a schedule.*
+/// ### &ensp; attr `#[add_to_render_app]`
+/// &emsp;&emsp; *Generates `add_<name>_to_render_app(app: &mut App, arg: T, ...)`, which builds the system and adds it to the `RenderApp` sub-app. Requires the `bevy_render` feature of `bevy`.*
 #[proc_macro_attribute]
 pub fn system_app_config(args: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_system_input!(input);
@@ -449,8 +2719,415 @@ pub fn system_app_config(args: TokenStream, input: TokenStream) -> TokenStream {
         input,
         args,
         parse_quote! { -> bevy::app::SystemAppConfig },
-        |params, system| {
-            quote! { (move |#params| #system).into_app_config() }
+        |span, system| {
+            quote_spanned! { span=> bevy::app::IntoSystemAppConfig::into_app_config(#system) }
         },
     )
 }
+
+/// Implements a Trait for a type, defining each of its system builder methods with a closure.
+///
+/// Each entry `method: |params| body` expands to a builder method whose system params are the
+/// params of the closure, which must be typed. Attributes written before an entry are kept on
+/// the method, so it can use any attribute of [`macro@system`]. Entries without `#[system_config]`
+/// or `#[system_app_config]` are expanded with `#[system]`.
+/// # Examples
+/// ```
+/// trait Interactive {
+///     #[system]
+///     fn update();
+///
+///     #[system_app_config]
+///     fn spawn(count: usize);
+/// }
+///
+/// #[derive(Component)]
+/// struct Rock;
+///
+/// impl_system_for!(Rock as Interactive {
+///     update: |rocks: Query<&Transform, With<Rock>>| {
+///         for rock in &rocks {
+///             // ...
+///         }
+///     },
+///     #[system_app_config(count: usize)]
+///     #[on_startup]
+///     spawn: |mut commands: Commands| {
+///         for _ in 0..count {
+///             commands.spawn(Rock);
+///         }
+///     },
+/// });
+/// ```
+#[proc_macro]
+pub fn impl_system_for(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as ImplSystemFor)
+        .into_token_stream()
+        .into()
+}
+
+/// Macro to build a system from a closure and builder args, outside of a trait.
+///
+/// Expands to a closure that takes the builder args and returns a `BoxedSystem`, like a method
+/// marked with `#[system]`. The system closure is made `move`, so it captures the builder args it
+/// uses.
+/// # Examples
+/// ```
+/// fn run() {
+///     let move_by = expand_system!((speed: f32) => |mut transforms: Query<&mut Transform>| {
+///         for mut transform in &mut transforms {
+///             transform.translation.x += speed;
+///         }
+///     });
+///
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_system(move_by(2.0))
+///         .run();
+/// }
+/// ```
+#[proc_macro]
+pub fn expand_system(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as ExpandSystem)
+        .into_token_stream()
+        .into()
+}
+
+/// Derive to add the systems of an implementer of Traits to an `App` by passing the implementer
+/// itself to `add_systems`.
+///
+/// List the system builder methods to call with `#[systems(Trait::method, ...)]`. Builder methods
+/// that take args are listed with them, e.g. `Trait::method(42)`, and may be followed by scheduling
+/// calls, e.g. `Trait::method.in_schedule(CoreSchedule::Startup)`. Methods with a receiver cannot be
+/// listed.
+/// # Examples
+/// ```
+/// trait Interactive {
+///     #[system]
+///     fn update();
+///
+///     #[system_app_config]
+///     fn spawn(count: usize);
+/// }
+///
+/// #[derive(Component, Systems)]
+/// #[systems(Interactive::update, Interactive::spawn(3))]
+/// struct Cactus;
+///
+/// impl Interactive for Cactus {
+///     // ...
+/// }
+///
+/// fn run() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_systems(Cactus) // Adds `Cactus::update()` and `Cactus::spawn(3)`
+///         .run();
+/// }
+/// ```
+#[proc_macro_derive(Systems, attributes(systems))]
+pub fn systems(input: TokenStream) -> TokenStream {
+    impl_systems(&parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Attribute to generate a `SystemSet` for a Trait, and place the systems built by the Trait in it.
+///
+/// On a trait, generates an enum named after the trait, e.g. `InteractiveSet`, with a variant for
+/// each method marked with `#[system_config]` or `#[system_app_config]`, e.g. `Update` for
+/// `update`. Add the attribute to each impl of the trait too, so the systems built by the
+/// implementer are placed in the matching variant. Systems built by `#[system]` are not placed in
+/// a set, since a `BoxedSystem` does not carry scheduling metadata.
+///
+/// The systems are also placed in a set of every system built by the trait, e.g.
+/// `InteractiveSystems`, which other builders can be ordered against with `#[before_all_of(Trait)]`
+/// and `#[after_all_of(Trait)]`.
+///
+/// Builder attributes nested in `#[cfg_attr(predicate, ...)]` are recognized too, in which case the
+/// variant only exists under `predicate`.
+///
+/// The trait also gets a provided `add_all_systems_to(app)` method, which adds the systems built
+/// by every builder method of the trait that takes no args, e.g.
+/// `Cactus::add_all_systems_to(&mut app)`. The trait cannot define an item of that name itself.
+/// # Examples
+/// ```
+/// #[system_set]
+/// trait Interactive {
+///     #[system_config]
+///     fn update();
+/// }
+///
+/// #[derive(Component)]
+/// struct Cactus;
+///
+/// #[system_set]
+/// impl Interactive for Cactus {
+///     #[system_config]
+///     fn update(cacti: Query<&GlobalTransform, With<Cactus>>) {
+///         // ...
+///     }
+/// }
+///
+/// fn run() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_system(Cactus::update()) // This system is in `InteractiveSet::Update` ...
+///         .configure_set(InteractiveSet::Update.before(CoreSet::PostUpdate)) // ... which can be ordered
+///         .run();
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn system_set(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`#[system_set]` does not take arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    impl_system_set(parse_macro_input!(input as Item))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Attribute to generate a `ScheduleLabel` for a Trait, to run the systems built by the Trait in a
+/// schedule driven by the user.
+///
+/// Generates a unit struct named after the trait, e.g. `InteractiveSchedule`, with an `add_system`
+/// helper that creates the schedule on first use and a `run` helper that runs it on a `World`.
+/// Builder methods marked with `#[add_to_schedule]` can also be passed the label directly.
+/// # Examples
+/// ```
+/// #[system_schedule]
+/// trait Interactive {
+///     #[system]
+///     fn update();
+/// }
+///
+/// #[derive(Component)]
+/// struct Cactus;
+///
+/// impl Interactive for Cactus {
+///     #[system]
+///     fn update(cacti: Query<&GlobalTransform, With<Cactus>>) {
+///         // ...
+///     }
+/// }
+///
+/// fn run_interactive(world: &mut World) {
+///     InteractiveSchedule::run(world); // Runs every system added to the schedule
+/// }
+///
+/// fn run() {
+///     let mut app = App::new();
+///
+///     InteractiveSchedule::add_system(&mut app, Cactus::update());
+///
+///     app.add_plugins(DefaultPlugins)
+///         .add_system(run_interactive)
+///         .run();
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn system_schedule(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`#[system_schedule]` does not take arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    impl_system_schedule(parse_macro_input!(input as Item))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Attribute to make every system builder method of a Trait, or of an impl of a Trait, fallible.
+///
+/// Adds `#[fallible]` to each builder method with a body, so its body returns
+/// `Result<(), Box<dyn Error>>` and can use `?`. Methods that already have `#[fallible]` or
+/// `#[on_error]` keep their own error handling. Add the attribute to each impl of the trait too, so
+/// the bodies written by implementers are fallible as well. A builder attribute nested in
+/// `#[cfg_attr(predicate, ...)]` makes the method fallible only under `predicate`.
+/// # Examples
+/// ```
+/// #[fallible_systems]
+/// trait Interactive {
+///     #[system]
+///     fn update();
+/// }
+///
+/// #[derive(Component)]
+/// struct Cactus(String);
+///
+/// #[fallible_systems]
+/// impl Interactive for Cactus {
+///     #[system]
+///     fn update(cacti: Query<&Cactus>) {
+///         for cactus in &cacti {
+///             let height: f32 = cactus.0.parse()?; // Errors are logged
+///         }
+///
+///         Ok(())
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn fallible_systems(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`#[fallible_systems]` does not take arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    impl_fallible_systems(parse_macro_input!(input as Item))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Attribute to implement `Plugin` for an implementer of Traits, so adding the implementer as a
+/// plugin adds its systems.
+///
+/// List the system builder methods to call as the args of the attribute, like with
+/// [`macro@Systems`]. Each one may be followed by scheduling calls, e.g.
+/// `Trait::method.in_schedule(CoreSchedule::Startup)`; these need the matching Bevy traits in
+/// scope, as with any other use of the builders.
+/// # Examples
+/// ```
+/// trait Interactive {
+///     #[system]
+///     fn update();
+///
+///     #[system_config]
+///     fn spawn(count: usize);
+/// }
+///
+/// #[derive(Component)]
+/// #[impl_plugin(
+///     Interactive::update,
+///     Interactive::spawn(3).in_schedule(CoreSchedule::Startup),
+/// )]
+/// struct Cactus;
+///
+/// impl Interactive for Cactus {
+///     // ...
+/// }
+///
+/// fn run() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(Cactus) // Adds `Cactus::update()` and `Cactus::spawn(3)`
+///         .run();
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn impl_plugin(args: TokenStream, input: TokenStream) -> TokenStream {
+    let systems = parse_macro_input!(args with Punctuated::<SystemCall, Comma>::parse_terminated);
+    let input = parse_macro_input!(input as DeriveInput);
+
+    impl_plugin_for(&systems, &input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Macro to merge the systems of several Traits, each implemented by a list of types, into a single
+/// `Plugin`.
+///
+/// Each trait must be marked with `#[system_set]`, which provides an `add_all_systems_to` method that
+/// adds the systems built by the builder methods of the trait that take no args. Since every system
+/// is built by the builder methods of its trait, the systems keep the sets and ordering declared on
+/// the trait and its impls, e.g. the systems of `Cactus` and `Rock` both land in
+/// `InteractiveSet::Update`. Builder methods that take args are not added.
+/// # Examples
+/// ```
+/// #[system_set]
+/// trait Interactive {
+///     #[system_config]
+///     fn update();
+/// }
+///
+/// #[system_set]
+/// trait Initializable {
+///     #[system_app_config]
+///     fn init();
+/// }
+///
+/// #[system_set]
+/// impl Interactive for Cactus {
+///     // ...
+/// }
+///
+/// #[system_set]
+/// impl Interactive for Rock {
+///     // ...
+/// }
+///
+/// #[system_set]
+/// impl Initializable for Cactus {
+///     #[system_app_config]
+///     #[on_startup]
+///     fn init(mut commands: Commands) {
+///         commands.spawn(Cactus);
+///     }
+/// }
+///
+/// fn run() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(merge_trait_plugins!(Interactive: [Cactus, Rock], Initializable: [Cactus]))
+///         .run();
+/// }
+/// ```
+#[proc_macro]
+pub fn merge_trait_plugins(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as MergeTraitPlugins)
+        .into_token_stream()
+        .into()
+}
+
+/// Macro to generate an extension trait on `App` that adds the systems of implementers of a
+/// Trait.
+///
+/// The trait must be marked with `#[system_set]`. The extension trait is named after it, e.g.
+/// `InteractiveAppExt`, and has a single method, e.g. `add_interactive::<T>()`, which adds the
+/// systems built by the builder methods of the trait that take no args. Each system keeps the
+/// scheduling metadata of its builder, so the systems are not moved to another schedule.
+/// # Examples
+/// ```
+/// #[system_set]
+/// pub trait Interactive {
+///     #[system_config]
+///     fn update();
+/// }
+///
+/// systems_from_trait!(pub Interactive);
+///
+/// #[derive(Component)]
+/// struct Cactus;
+///
+/// #[system_set]
+/// impl Interactive for Cactus {
+///     // ...
+/// }
+///
+/// fn run() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_interactive::<Cactus>()
+///         .run();
+/// }
+/// ```
+#[proc_macro]
+pub fn systems_from_trait(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as SystemsFromTrait)
+        .into_token_stream()
+        .into()
+}